@@ -12,14 +12,19 @@
  ********************************************************************************/
 use crate::UPTransportZenoh;
 use async_trait::async_trait;
-use std::{string::ToString, sync::Arc, time::Duration};
+use std::{collections::HashMap, string::ToString, sync::Arc, time::Duration};
+use tokio::{sync::RwLock, task::JoinHandle};
 use tracing::error;
 use up_rust::{
-    communication::{CallOptions, RpcClient, ServiceInvocationError, UPayload},
-    LocalUriProvider, UAttributes, UCode, UMessageType, UPayloadFormat, UPriority, UStatus, UUri,
-    UUID,
+    communication::{CallOptions, RequestHandler, RpcClient, ServiceInvocationError, UPayload},
+    LocalUriProvider, UAttributes, UCode, UMessage, UMessageType, UPayloadFormat, UPriority,
+    UStatus, UUri, UUID,
+};
+use zenoh::{
+    liveliness::LivelinessToken,
+    prelude::r#async::*,
+    queryable::{Query, Queryable},
 };
-use zenoh::prelude::r#async::*;
 
 pub struct ZenohRpcClient {
     transport: Arc<UPTransportZenoh>,
@@ -40,14 +45,22 @@ impl ZenohRpcClient {
     }
 }
 
-#[async_trait]
-impl RpcClient for ZenohRpcClient {
-    async fn invoke_method(
+impl ZenohRpcClient {
+    /// Sends a uProtocol RPC request to `method` as a Zenoh query targeted at `target`,
+    /// returning the channel on which the resulting replies are delivered.
+    ///
+    /// For `QueryTarget::BestMatching` (the default used by [`RpcClient::invoke_method`]),
+    /// Zenoh delivers at most one reply. For `QueryTarget::All` or
+    /// `QueryTarget::AllComplete`, every queryable matching `method` that is currently alive
+    /// may reply, so callers interested in more than the first reply should use
+    /// [`ZenohRpcClient::invoke_method_all`].
+    async fn query_method(
         &self,
-        method: UUri,
-        call_options: CallOptions,
+        method: &UUri,
+        call_options: &CallOptions,
         payload: Option<UPayload>,
-    ) -> Result<Option<UPayload>, ServiceInvocationError> {
+        target: QueryTarget,
+    ) -> Result<flume::Receiver<Reply>, ServiceInvocationError> {
         // Get data and format from UPayload
         let mut payload_data = None;
         let mut payload_format = UPayloadFormat::UPAYLOAD_FORMAT_UNSPECIFIED;
@@ -75,9 +88,7 @@ impl RpcClient for ZenohRpcClient {
         };
 
         // Get Zenoh key
-        let zenoh_key = self
-            .transport
-            .to_zenoh_key_string(&source_uri, Some(&method));
+        let zenoh_key = self.transport.to_zenoh_key_string(&source_uri, Some(method));
 
         // Put UAttributes into Zenoh user attachment
         let Ok(attachment) = UPTransportZenoh::uattributes_to_attachment(&attributes) else {
@@ -93,18 +104,200 @@ impl RpcClient for ZenohRpcClient {
             None => getbuilder,
         }
         .with_attachment(attachment.build())
-        .target(QueryTarget::BestMatching)
+        .target(target)
         .timeout(Duration::from_millis(u64::from(call_options.ttl())));
-        let Ok(replies) = getbuilder.res().await else {
-            let msg = "Error while sending Zenoh query".to_string();
+        getbuilder.res().await.map_err(|e| {
+            let msg = format!("Error while sending Zenoh query: {e:?}");
             error!("{msg}");
-            return Err(ServiceInvocationError::RpcError(UStatus {
+            ServiceInvocationError::RpcError(UStatus {
                 code: UCode::INTERNAL.into(),
                 message: Some(msg),
                 ..Default::default()
-            }));
+            })
+        })
+    }
+
+    /// Decodes a single Zenoh reply to an RPC request into the corresponding uProtocol result.
+    fn decode_reply(reply: Reply) -> Result<UPayload, ServiceInvocationError> {
+        match reply.sample {
+            Ok(sample) => {
+                let reply_attributes = sample
+                    .attachment()
+                    .and_then(|a| UPTransportZenoh::attachment_to_uattributes(a).ok());
+                let reply_data = sample.payload.contiguous().to_vec();
+
+                // A server that failed to process the request reports the resulting status
+                // code in the reply's `commstatus` attribute (see `ZenohRpcServer::reply`),
+                // with the corresponding message carried as the reply's payload.
+                if let Some(commstatus) = reply_attributes
+                    .as_ref()
+                    .and_then(|attr| attr.commstatus)
+                    .filter(|code| *code != UCode::OK.into())
+                {
+                    let code = UCode::from_i32(commstatus).unwrap_or(UCode::INTERNAL);
+                    let message = String::from_utf8(reply_data).unwrap_or_default();
+                    return Err(commstatus_to_service_invocation_error(code, message));
+                }
+
+                let payload_format = reply_attributes
+                    .map(|attr| attr.payload_format.enum_value_or_default())
+                    .unwrap_or_default();
+                Ok(UPayload::new(reply_data.into(), payload_format))
+            }
+            Err(e) => {
+                let msg = format!("Error while parsing Zenoh reply: {e:?}");
+                error!("{msg}");
+                Err(ServiceInvocationError::RpcError(UStatus {
+                    code: UCode::INTERNAL.into(),
+                    message: Some(msg),
+                    ..Default::default()
+                }))
+            }
+        }
+    }
+
+    /// Invokes an RPC method and collects the responses from every replier that answers
+    /// before the request's TTL elapses, instead of just the first one.
+    ///
+    /// This is useful when `target` is [`QueryTarget::All`] or
+    /// [`QueryTarget::AllComplete`] and several servers are expected to be serving the same
+    /// method, e.g. for broadcasting a request to a group of identical services.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The URI of the method to invoke.
+    /// * `call_options` - The RPC call options, e.g. the time-to-live for the request.
+    /// * `payload` - The payload to send with the request, if any.
+    /// * `target` - Which of the repliers matching `method` should be queried.
+    pub async fn invoke_method_all(
+        &self,
+        method: UUri,
+        call_options: CallOptions,
+        payload: Option<UPayload>,
+        target: QueryTarget,
+    ) -> Vec<Result<UPayload, ServiceInvocationError>> {
+        let replies = match self
+            .query_method(&method, &call_options, payload, target)
+            .await
+        {
+            Ok(replies) => replies,
+            Err(e) => return vec![Err(e)],
         };
 
+        let mut results = Vec::new();
+        while let Ok(reply) = replies.recv_async().await {
+            results.push(Self::decode_reply(reply));
+        }
+        results
+    }
+
+    /// Discovers which method URIs matching `filter` are currently being served, i.e. have a
+    /// live [`ZenohRpcServer`] endpoint registered for them.
+    ///
+    /// This lets an application avoid invoking a method that nobody is currently serving,
+    /// instead of waiting for the RPC call to time out.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The method URI to discover servers for. Wildcard segments (e.g. `*`/`FFFF`)
+    ///   are supported, matching any endpoint registered for a method within that range.
+    pub async fn discover_methods(&self, filter: &UUri) -> Result<Vec<UUri>, UStatus> {
+        let liveliness_key = method_to_liveliness_key(filter);
+        let replies = self
+            .transport
+            .session
+            .liveliness()
+            .get(&liveliness_key)
+            .res()
+            .await
+            .map_err(|e| {
+                UStatus::fail_with_code(
+                    UCode::INTERNAL,
+                    format!("unable to query Zenoh liveliness tokens for [{filter:?}]: {e:?}"),
+                )
+            })?;
+
+        let mut methods = Vec::new();
+        while let Ok(reply) = replies.recv_async().await {
+            match reply.sample {
+                Ok(sample) => {
+                    if let Some(method) = liveliness_key_to_method(sample.key_expr.as_str()) {
+                        methods.push(method);
+                    }
+                }
+                Err(e) => {
+                    error!("Error while parsing Zenoh liveliness reply: {e:?}");
+                }
+            }
+        }
+        Ok(methods)
+    }
+
+    /// Subscribes to liveliness changes of the RPC endpoints matching `filter`, invoking
+    /// `callback` with the corresponding method URI whenever a server starts or stops serving
+    /// it.
+    ///
+    /// The callback is also invoked with `(method, true)` for every endpoint matching `filter`
+    /// that is already alive at subscription time, so that callers do not have to separately
+    /// call [`ZenohRpcClient::discover_methods`] to learn the current state.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The method URI to watch for liveliness changes. Wildcard segments are
+    ///   supported, as in [`ZenohRpcClient::discover_methods`].
+    /// * `callback` - Invoked with `(method, true)` when an endpoint for `method` appears, and
+    ///   `(method, false)` when it disappears.
+    ///
+    /// Returns a handle to the background task driving the subscription; aborting it stops the
+    /// subscription. Dropping the handle detaches the task, which then keeps running.
+    pub async fn subscribe_liveliness<F>(
+        &self,
+        filter: &UUri,
+        mut callback: F,
+    ) -> Result<JoinHandle<()>, UStatus>
+    where
+        F: FnMut(UUri, bool) + Send + 'static,
+    {
+        let liveliness_key = method_to_liveliness_key(filter);
+        let subscriber = self
+            .transport
+            .session
+            .liveliness()
+            .declare_subscriber(&liveliness_key)
+            .history(true)
+            .res()
+            .await
+            .map_err(|e| {
+                UStatus::fail_with_code(
+                    UCode::INTERNAL,
+                    format!(
+                        "unable to subscribe to Zenoh liveliness tokens for [{filter:?}]: {e:?}"
+                    ),
+                )
+            })?;
+
+        Ok(tokio::spawn(async move {
+            while let Ok(sample) = subscriber.recv_async().await {
+                if let Some(method) = liveliness_key_to_method(sample.key_expr.as_str()) {
+                    callback(method, sample.kind == SampleKind::Put);
+                }
+            }
+        }))
+    }
+}
+
+#[async_trait]
+impl RpcClient for ZenohRpcClient {
+    async fn invoke_method(
+        &self,
+        method: UUri,
+        call_options: CallOptions,
+        payload: Option<UPayload>,
+    ) -> Result<Option<UPayload>, ServiceInvocationError> {
+        let replies = self
+            .query_method(&method, &call_options, payload, QueryTarget::BestMatching)
+            .await?;
+
         // Receive the reply
         let Ok(reply) = replies.recv_async().await else {
             let msg = "Error while receiving Zenoh reply".to_string();
@@ -115,26 +308,603 @@ impl RpcClient for ZenohRpcClient {
                 ..Default::default()
             }));
         };
-        match reply.sample {
-            Ok(sample) => {
-                let payload_format = sample
-                    .attachment()
-                    .and_then(|a| UPTransportZenoh::attachment_to_uattributes(a).ok())
-                    .map(|attr| attr.payload_format.enum_value_or_default());
-                Ok(Some(UPayload::new(
-                    sample.payload.contiguous().to_vec().into(),
-                    payload_format.unwrap_or_default(),
-                )))
+        Self::decode_reply(reply).map(Some)
+    }
+}
+
+/// An endpoint that is currently being served by a [`ZenohRpcServer`].
+struct RegisteredEndpoint {
+    /// Handle to the task that is polling the Zenoh queryable for incoming requests.
+    ///
+    /// Aborting this task undeclares the queryable, which stops Zenoh from routing any
+    /// further queries to this endpoint.
+    listener_task: JoinHandle<()>,
+    /// The liveliness token that announces this endpoint to `ZenohRpcClient::discover_methods`
+    /// and `ZenohRpcClient::subscribe_liveliness` for as long as it remains registered.
+    ///
+    /// Dropping it (e.g. when the endpoint is removed from `ZenohRpcServer::endpoints`)
+    /// undeclares the token, notifying any liveliness subscribers that this endpoint is gone.
+    _liveliness_token: LivelinessToken<'static>,
+}
+
+/// A server for answering uProtocol RPC requests that are dispatched to it via Zenoh queries.
+///
+/// For each method that an application wants to serve, it registers a [`RequestHandler`]
+/// via [`ZenohRpcServer::register_endpoint`]. Incoming Zenoh queries for that method's key are
+/// then decoded into [`UMessage`]s of type `UMESSAGE_TYPE_REQUEST`, passed to the handler, and
+/// the handler's response is sent back as the reply to the query.
+pub struct ZenohRpcServer {
+    transport: Arc<UPTransportZenoh>,
+    /// `None` marks a method that is in the process of being registered: it reserves the
+    /// method so that a concurrent `register_endpoint` call for it is rejected immediately,
+    /// without holding a lock for the duration of the Zenoh declarations that resolve it to
+    /// `Some(RegisteredEndpoint)` (see `ZenohRpcServer::register_endpoint`).
+    endpoints: Arc<RwLock<HashMap<UUri, Option<RegisteredEndpoint>>>>,
+}
+
+impl ZenohRpcServer {
+    /// Creates a new RPC server for the Zenoh transport.
+    ///
+    /// # Arguments
+    ///
+    /// * `transport` - The Zenoh uProtocol Transport Layer.
+    pub fn new(transport: Arc<UPTransportZenoh>) -> Self {
+        ZenohRpcServer {
+            transport,
+            endpoints: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Registers a handler that answers RPC requests sent to a given method.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The URI of the method to serve.
+    /// * `request_handler` - The handler to invoke for each request received for `method`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an endpoint is already registered for `method`, or if the
+    /// corresponding Zenoh queryable could not be declared.
+    pub async fn register_endpoint(
+        &self,
+        method: UUri,
+        request_handler: Arc<dyn RequestHandler>,
+    ) -> Result<(), UStatus> {
+        // Reserve `method` under a short-lived write lock, so a concurrent registration for
+        // the same method is rejected immediately instead of racing past this check while the
+        // (comparatively slow) Zenoh declarations below are in flight. The reservation is then
+        // resolved into a full `RegisteredEndpoint`, or removed again on failure, without
+        // holding the lock for the duration of those declarations, so registering/unregistering
+        // unrelated methods isn't blocked behind them.
+        {
+            let mut endpoints = self.endpoints.write().await;
+            if endpoints.contains_key(&method) {
+                return Err(UStatus::fail_with_code(
+                    UCode::ALREADY_EXISTS,
+                    format!("an RPC endpoint has already been registered for [{method:?}]"),
+                ));
+            }
+            endpoints.insert(method.clone(), None);
+        }
+
+        let zenoh_key = self.transport.to_zenoh_key_string(&method, None);
+        let queryable = match self
+            .transport
+            .session
+            .declare_queryable(&zenoh_key)
+            .res()
+            .await
+        {
+            Ok(queryable) => queryable,
+            Err(e) => {
+                self.endpoints.write().await.remove(&method);
+                return Err(UStatus::fail_with_code(
+                    UCode::INTERNAL,
+                    format!("unable to declare Zenoh queryable for [{method:?}]: {e:?}"),
+                ));
             }
+        };
+
+        // Announce this endpoint so that `ZenohRpcClient::discover_methods` and
+        // `ZenohRpcClient::subscribe_liveliness` can find it. The liveliness key is encoded
+        // and decoded entirely within this module (see `method_to_liveliness_key`), so
+        // discovery does not depend on inverting `UPTransportZenoh::to_zenoh_key_string`.
+        let liveliness_key = method_to_liveliness_key(&method);
+        let liveliness_token = match self
+            .transport
+            .session
+            .liveliness()
+            .declare_token(&liveliness_key)
+            .res()
+            .await
+        {
+            Ok(token) => token,
             Err(e) => {
-                let msg = format!("Error while parsing Zenoh reply: {e:?}");
-                error!("{msg}");
-                return Err(ServiceInvocationError::RpcError(UStatus {
-                    code: UCode::INTERNAL.into(),
-                    message: Some(msg),
-                    ..Default::default()
-                }));
+                self.endpoints.write().await.remove(&method);
+                return Err(UStatus::fail_with_code(
+                    UCode::INTERNAL,
+                    format!("unable to declare Zenoh liveliness token for [{method:?}]: {e:?}"),
+                ));
+            }
+        };
+
+        let listener_task = tokio::spawn(Self::run_queryable(queryable, request_handler));
+
+        self.endpoints.write().await.insert(
+            method,
+            Some(RegisteredEndpoint {
+                listener_task,
+                _liveliness_token: liveliness_token,
+            }),
+        );
+        Ok(())
+    }
+
+    /// Unregisters the handler that has been serving RPC requests for a given method.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The URI of the method that was previously passed to
+    ///   [`ZenohRpcServer::register_endpoint`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no endpoint is currently registered for `method`.
+    pub async fn unregister_endpoint(&self, method: &UUri) -> Result<(), UStatus> {
+        match self.endpoints.write().await.remove(method) {
+            Some(Some(endpoint)) => {
+                endpoint.listener_task.abort();
+                Ok(())
+            }
+            Some(None) => Err(UStatus::fail_with_code(
+                UCode::UNAVAILABLE,
+                format!("the RPC endpoint for [{method:?}] is still being registered"),
+            )),
+            None => Err(UStatus::fail_with_code(
+                UCode::NOT_FOUND,
+                format!("no RPC endpoint is registered for [{method:?}]"),
+            )),
+        }
+    }
+
+    /// Unregisters all endpoints, stopping this server from answering any further RPC requests.
+    pub async fn shutdown(&self) {
+        for (_method, endpoint) in self.endpoints.write().await.drain() {
+            if let Some(endpoint) = endpoint {
+                endpoint.listener_task.abort();
+            }
+        }
+    }
+
+    /// Polls a Zenoh queryable for incoming queries and dispatches each of them to the
+    /// given handler, until the queryable is closed (or this task is aborted).
+    async fn run_queryable(queryable: Queryable<'_, flume::Receiver<Query>>, request_handler: Arc<dyn RequestHandler>) {
+        while let Ok(query) = queryable.recv_async().await {
+            let request_handler = request_handler.clone();
+            tokio::spawn(async move {
+                Self::handle_query(query, request_handler).await;
+            });
+        }
+    }
+
+    /// Decodes a Zenoh query into a uProtocol request, invokes the handler, and sends the
+    /// handler's response back as the query's reply.
+    async fn handle_query(query: Query, request_handler: Arc<dyn RequestHandler>) {
+        let Some(attachment) = query.attachment() else {
+            error!("Ignoring Zenoh query that is missing the user attachment with UAttributes");
+            return;
+        };
+        let request_attributes = match UPTransportZenoh::attachment_to_uattributes(attachment) {
+            Ok(attributes) => attributes,
+            Err(e) => {
+                error!("Unable to parse UAttributes from Zenoh query attachment: {e:?}");
+                return;
             }
+        };
+
+        let request_payload = query
+            .value()
+            .map(|value| value.payload.contiguous().to_vec());
+        let request_message = UMessage {
+            attributes: Some(request_attributes.clone()).into(),
+            payload: request_payload.map(Into::into),
+            ..Default::default()
+        };
+
+        let response = request_handler.handle_request(request_message).await;
+        if let Err(e) = Self::reply(&query, &request_attributes, response).await {
+            error!("{e}");
         }
     }
+
+    /// Sends the outcome of invoking a [`RequestHandler`] back to the caller as the reply to
+    /// the Zenoh query that carried the original request.
+    ///
+    /// `request_attributes` are the attributes of the original request; its `source` becomes
+    /// the reply's `sink`, and its `id` is carried as the reply's `reqid` so that the caller
+    /// can correlate the response with the request it sent.
+    async fn reply(
+        query: &Query,
+        request_attributes: &UAttributes,
+        response: Result<Option<UPayload>, ServiceInvocationError>,
+    ) -> Result<(), String> {
+        let (payload_data, payload_format, commstatus) = match response {
+            Ok(payload) => {
+                let (data, format) = payload
+                    .map(|p| (p.payload(), p.payload_format()))
+                    .unwrap_or_else(|| (vec![].into(), UPayloadFormat::UPAYLOAD_FORMAT_UNSPECIFIED));
+                (data, format, UCode::OK)
+            }
+            Err(e) => {
+                error!("RequestHandler failed to process RPC request: {e:?}");
+                let (code, message) = service_invocation_error_to_commstatus(&e);
+                (
+                    message.into_bytes().into(),
+                    UPayloadFormat::UPAYLOAD_FORMAT_TEXT,
+                    code,
+                )
+            }
+        };
+
+        let attributes = UAttributes {
+            type_: UMessageType::UMESSAGE_TYPE_RESPONSE.into(),
+            id: Some(UUID::build()).into(),
+            source: request_attributes.sink.clone(),
+            sink: request_attributes.source.clone(),
+            reqid: request_attributes.id.clone(),
+            commstatus: Some(commstatus.into()),
+            payload_format: payload_format.into(),
+            ..Default::default()
+        };
+
+        let Ok(attachment) = UPTransportZenoh::uattributes_to_attachment(&attributes) else {
+            return Err("Unable to transform UAttributes to user attachment in Zenoh".to_string());
+        };
+
+        query
+            .reply(Ok(Sample::new(query.key_expr().clone(), payload_data.as_ref())))
+            .with_attachment(attachment.build())
+            .res()
+            .await
+            .map_err(|e| format!("Error while sending Zenoh reply: {e:?}"))
+    }
+}
+
+/// Maps a [`ServiceInvocationError`] reported by a [`RequestHandler`] to the `UCode` that gets
+/// put into a reply's `commstatus` attribute, together with the error's message.
+fn service_invocation_error_to_commstatus(error: &ServiceInvocationError) -> (UCode, String) {
+    match error {
+        ServiceInvocationError::Cancelled(msg) => (UCode::CANCELLED, msg.clone()),
+        ServiceInvocationError::Unknown(msg) => (UCode::UNKNOWN, msg.clone()),
+        ServiceInvocationError::InvalidArgument(msg) => (UCode::INVALID_ARGUMENT, msg.clone()),
+        ServiceInvocationError::DeadlineExceeded(msg) => (UCode::DEADLINE_EXCEEDED, msg.clone()),
+        ServiceInvocationError::NotFound(msg) => (UCode::NOT_FOUND, msg.clone()),
+        ServiceInvocationError::AlreadyExists(msg) => (UCode::ALREADY_EXISTS, msg.clone()),
+        ServiceInvocationError::PermissionDenied(msg) => (UCode::PERMISSION_DENIED, msg.clone()),
+        ServiceInvocationError::ResourceExhausted(msg) => (UCode::RESOURCE_EXHAUSTED, msg.clone()),
+        ServiceInvocationError::FailedPrecondition(msg) => {
+            (UCode::FAILED_PRECONDITION, msg.clone())
+        }
+        ServiceInvocationError::Aborted(msg) => (UCode::ABORTED, msg.clone()),
+        ServiceInvocationError::OutOfRange(msg) => (UCode::OUT_OF_RANGE, msg.clone()),
+        ServiceInvocationError::Unimplemented(msg) => (UCode::UNIMPLEMENTED, msg.clone()),
+        ServiceInvocationError::Internal(msg) => (UCode::INTERNAL, msg.clone()),
+        ServiceInvocationError::Unavailable(msg) => (UCode::UNAVAILABLE, msg.clone()),
+        ServiceInvocationError::DataLoss(msg) => (UCode::DATA_LOSS, msg.clone()),
+        ServiceInvocationError::Unauthenticated(msg) => (UCode::UNAUTHENTICATED, msg.clone()),
+        ServiceInvocationError::RpcError(status) => (
+            status.code.enum_value_or(UCode::INTERNAL),
+            status.message.clone().unwrap_or_default(),
+        ),
+    }
+}
+
+/// Maps a `UCode`/message pair received in a reply's `commstatus` attribute back to the
+/// [`ServiceInvocationError`] variant that [`RpcClient::invoke_method`] returns to the caller.
+fn commstatus_to_service_invocation_error(code: UCode, message: String) -> ServiceInvocationError {
+    match code {
+        UCode::CANCELLED => ServiceInvocationError::Cancelled(message),
+        UCode::UNKNOWN => ServiceInvocationError::Unknown(message),
+        UCode::INVALID_ARGUMENT => ServiceInvocationError::InvalidArgument(message),
+        UCode::DEADLINE_EXCEEDED => ServiceInvocationError::DeadlineExceeded(message),
+        UCode::NOT_FOUND => ServiceInvocationError::NotFound(message),
+        UCode::ALREADY_EXISTS => ServiceInvocationError::AlreadyExists(message),
+        UCode::PERMISSION_DENIED => ServiceInvocationError::PermissionDenied(message),
+        UCode::RESOURCE_EXHAUSTED => ServiceInvocationError::ResourceExhausted(message),
+        UCode::FAILED_PRECONDITION => ServiceInvocationError::FailedPrecondition(message),
+        UCode::ABORTED => ServiceInvocationError::Aborted(message),
+        UCode::OUT_OF_RANGE => ServiceInvocationError::OutOfRange(message),
+        UCode::UNIMPLEMENTED => ServiceInvocationError::Unimplemented(message),
+        UCode::UNAVAILABLE => ServiceInvocationError::Unavailable(message),
+        UCode::DATA_LOSS => ServiceInvocationError::DataLoss(message),
+        UCode::UNAUTHENTICATED => ServiceInvocationError::Unauthenticated(message),
+        _ => ServiceInvocationError::Internal(message),
+    }
+}
+
+#[cfg(test)]
+mod commstatus_tests {
+    use super::*;
+
+    #[test]
+    fn service_invocation_error_to_commstatus_maps_every_variant_to_its_own_code() {
+        let cases = [
+            (
+                ServiceInvocationError::Cancelled("msg".to_string()),
+                UCode::CANCELLED,
+            ),
+            (
+                ServiceInvocationError::Unknown("msg".to_string()),
+                UCode::UNKNOWN,
+            ),
+            (
+                ServiceInvocationError::InvalidArgument("msg".to_string()),
+                UCode::INVALID_ARGUMENT,
+            ),
+            (
+                ServiceInvocationError::DeadlineExceeded("msg".to_string()),
+                UCode::DEADLINE_EXCEEDED,
+            ),
+            (
+                ServiceInvocationError::NotFound("msg".to_string()),
+                UCode::NOT_FOUND,
+            ),
+            (
+                ServiceInvocationError::AlreadyExists("msg".to_string()),
+                UCode::ALREADY_EXISTS,
+            ),
+            (
+                ServiceInvocationError::PermissionDenied("msg".to_string()),
+                UCode::PERMISSION_DENIED,
+            ),
+            (
+                ServiceInvocationError::ResourceExhausted("msg".to_string()),
+                UCode::RESOURCE_EXHAUSTED,
+            ),
+            (
+                ServiceInvocationError::FailedPrecondition("msg".to_string()),
+                UCode::FAILED_PRECONDITION,
+            ),
+            (
+                ServiceInvocationError::Aborted("msg".to_string()),
+                UCode::ABORTED,
+            ),
+            (
+                ServiceInvocationError::OutOfRange("msg".to_string()),
+                UCode::OUT_OF_RANGE,
+            ),
+            (
+                ServiceInvocationError::Unimplemented("msg".to_string()),
+                UCode::UNIMPLEMENTED,
+            ),
+            (
+                ServiceInvocationError::Internal("msg".to_string()),
+                UCode::INTERNAL,
+            ),
+            (
+                ServiceInvocationError::Unavailable("msg".to_string()),
+                UCode::UNAVAILABLE,
+            ),
+            (
+                ServiceInvocationError::DataLoss("msg".to_string()),
+                UCode::DATA_LOSS,
+            ),
+            (
+                ServiceInvocationError::Unauthenticated("msg".to_string()),
+                UCode::UNAUTHENTICATED,
+            ),
+        ];
+
+        for (error, expected_code) in cases {
+            let (code, message) = service_invocation_error_to_commstatus(&error);
+            assert_eq!(code, expected_code);
+            assert_eq!(message, "msg");
+        }
+    }
+
+    #[test]
+    fn service_invocation_error_to_commstatus_uses_rpc_error_status_code_and_message() {
+        let (code, message) = service_invocation_error_to_commstatus(
+            &ServiceInvocationError::RpcError(UStatus {
+                code: UCode::RESOURCE_EXHAUSTED.into(),
+                message: Some("out of quota".to_string()),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(code, UCode::RESOURCE_EXHAUSTED);
+        assert_eq!(message, "out of quota");
+    }
+
+    #[test]
+    fn commstatus_to_service_invocation_error_maps_every_code_to_its_own_variant() {
+        let cases = [
+            (ServiceInvocationError::Cancelled("msg".to_string()), UCode::CANCELLED),
+            (ServiceInvocationError::Unknown("msg".to_string()), UCode::UNKNOWN),
+            (
+                ServiceInvocationError::InvalidArgument("msg".to_string()),
+                UCode::INVALID_ARGUMENT,
+            ),
+            (
+                ServiceInvocationError::DeadlineExceeded("msg".to_string()),
+                UCode::DEADLINE_EXCEEDED,
+            ),
+            (ServiceInvocationError::NotFound("msg".to_string()), UCode::NOT_FOUND),
+            (
+                ServiceInvocationError::AlreadyExists("msg".to_string()),
+                UCode::ALREADY_EXISTS,
+            ),
+            (
+                ServiceInvocationError::PermissionDenied("msg".to_string()),
+                UCode::PERMISSION_DENIED,
+            ),
+            (
+                ServiceInvocationError::ResourceExhausted("msg".to_string()),
+                UCode::RESOURCE_EXHAUSTED,
+            ),
+            (
+                ServiceInvocationError::FailedPrecondition("msg".to_string()),
+                UCode::FAILED_PRECONDITION,
+            ),
+            (ServiceInvocationError::Aborted("msg".to_string()), UCode::ABORTED),
+            (
+                ServiceInvocationError::OutOfRange("msg".to_string()),
+                UCode::OUT_OF_RANGE,
+            ),
+            (
+                ServiceInvocationError::Unimplemented("msg".to_string()),
+                UCode::UNIMPLEMENTED,
+            ),
+            (ServiceInvocationError::Internal("msg".to_string()), UCode::INTERNAL),
+            (
+                ServiceInvocationError::Unavailable("msg".to_string()),
+                UCode::UNAVAILABLE,
+            ),
+            (ServiceInvocationError::DataLoss("msg".to_string()), UCode::DATA_LOSS),
+            (
+                ServiceInvocationError::Unauthenticated("msg".to_string()),
+                UCode::UNAUTHENTICATED,
+            ),
+        ];
+
+        for (expected, code) in cases {
+            let mapped = commstatus_to_service_invocation_error(code, "msg".to_string());
+            assert_eq!(format!("{mapped:?}"), format!("{expected:?}"));
+        }
+    }
+
+    #[test]
+    fn commstatus_to_service_invocation_error_defaults_unknown_codes_to_internal() {
+        let mapped = commstatus_to_service_invocation_error(UCode::OK, "msg".to_string());
+        assert!(matches!(mapped, ServiceInvocationError::Internal(m) if m == "msg"));
+    }
+}
+
+/// The `uprotocol.v1.UUri` field value that denotes "match any" for that field.
+const UURI_WILDCARD_AUTHORITY: &str = "*";
+const UURI_WILDCARD_ENTITY_ID: u32 = 0x0000_FFFF;
+const UURI_WILDCARD_VERSION: u32 = 0x0000_00FF;
+const UURI_WILDCARD_RESOURCE_ID: u32 = 0x0000_FFFF;
+
+/// The key expression prefix under which RPC endpoint liveliness tokens are declared.
+///
+/// Discovery needs to turn a matched Zenoh key expression back into the `UUri` it was
+/// declared for. Rather than relying on being able to invert
+/// `UPTransportZenoh::to_zenoh_key_string` (whose encoding is private to the transport),
+/// [`method_to_liveliness_key`] and [`liveliness_key_to_method`] define and own a separate,
+/// fully reversible encoding used only for liveliness tokens.
+const LIVELINESS_KEY_PREFIX: &str = "up_rpc_liveliness";
+
+/// Builds the liveliness key expression that a [`ZenohRpcServer`] declares a liveliness token
+/// under for `method`, substituting a Zenoh wildcard segment (`*`) for any uProtocol wildcard
+/// present in one of `method`'s fields.
+fn method_to_liveliness_key(method: &UUri) -> String {
+    let authority = if method.authority_name == UURI_WILDCARD_AUTHORITY {
+        "*".to_string()
+    } else {
+        method.authority_name.clone()
+    };
+    let ue_id = if method.ue_id == UURI_WILDCARD_ENTITY_ID {
+        "*".to_string()
+    } else {
+        format!("{:X}", method.ue_id)
+    };
+    let ue_version_major = if method.ue_version_major == UURI_WILDCARD_VERSION {
+        "*".to_string()
+    } else {
+        format!("{:X}", method.ue_version_major)
+    };
+    let resource_id = if method.resource_id == UURI_WILDCARD_RESOURCE_ID {
+        "*".to_string()
+    } else {
+        format!("{:X}", method.resource_id)
+    };
+    format!("{LIVELINESS_KEY_PREFIX}/{authority}/{ue_id}/{ue_version_major}/{resource_id}")
+}
+
+/// Inverts [`method_to_liveliness_key`] for a concrete (non-wildcarded) key expression, as
+/// observed in a liveliness reply or subscription sample for an actually registered endpoint.
+fn liveliness_key_to_method(zenoh_key: &str) -> Option<UUri> {
+    let rest = zenoh_key
+        .strip_prefix(LIVELINESS_KEY_PREFIX)?
+        .strip_prefix('/')?;
+    let mut segments = rest.splitn(4, '/');
+    let authority_name = segments.next()?.to_string();
+    let ue_id = u32::from_str_radix(segments.next()?, 16).ok()?;
+    let ue_version_major = u32::from_str_radix(segments.next()?, 16).ok()?;
+    let resource_id = u32::from_str_radix(segments.next()?, 16).ok()?;
+    Some(UUri {
+        authority_name,
+        ue_id,
+        ue_version_major,
+        resource_id,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod liveliness_key_tests {
+    use super::*;
+
+    fn concrete_method() -> UUri {
+        UUri {
+            authority_name: "vehicle".to_string(),
+            ue_id: 0x1003,
+            ue_version_major: 1,
+            resource_id: 0x8A50,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn method_to_liveliness_key_encodes_concrete_fields_as_uppercase_hex() {
+        let key = method_to_liveliness_key(&concrete_method());
+        assert_eq!(key, "up_rpc_liveliness/vehicle/1003/1/8A50");
+    }
+
+    #[test]
+    fn liveliness_key_to_method_inverts_method_to_liveliness_key() {
+        let method = concrete_method();
+        let key = method_to_liveliness_key(&method);
+        assert_eq!(liveliness_key_to_method(&key), Some(method));
+    }
+
+    #[test]
+    fn method_to_liveliness_key_substitutes_zenoh_wildcard_for_each_uuri_wildcard() {
+        let method = UUri {
+            authority_name: UURI_WILDCARD_AUTHORITY.to_string(),
+            ue_id: UURI_WILDCARD_ENTITY_ID,
+            ue_version_major: UURI_WILDCARD_VERSION,
+            resource_id: UURI_WILDCARD_RESOURCE_ID,
+            ..Default::default()
+        };
+        assert_eq!(
+            method_to_liveliness_key(&method),
+            "up_rpc_liveliness/*/*/*/*"
+        );
+    }
+
+    #[test]
+    fn liveliness_key_to_method_rejects_a_key_with_the_wrong_prefix() {
+        assert_eq!(
+            liveliness_key_to_method("some/unrelated/key/expression/here"),
+            None
+        );
+    }
+
+    #[test]
+    fn liveliness_key_to_method_rejects_a_key_with_too_few_segments() {
+        assert_eq!(
+            liveliness_key_to_method("up_rpc_liveliness/vehicle/1003"),
+            None
+        );
+    }
+
+    #[test]
+    fn liveliness_key_to_method_rejects_a_non_hex_numeric_segment() {
+        assert_eq!(
+            liveliness_key_to_method("up_rpc_liveliness/vehicle/not_hex/1/8A50"),
+            None
+        );
+    }
 }